@@ -1,3 +1,4 @@
 pub mod first;
 pub mod second;
+pub mod small;
 pub mod third;
@@ -1,3 +1,11 @@
+use std::cmp::Ordering;
+use std::collections::{HashSet, LinkedList};
+use std::fmt;
+use std::hash::Hash;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+
 #[derive(Default)]
 pub struct List<T> {
     head: Link<T>,
@@ -9,16 +17,72 @@ pub struct List<T> {
 // あるいは、 list.head.is_some() とか
 type Link<T> = Option<Box<Node<T>>>;
 
-struct Node<T> {
+// retain_batched で取り除いたノードを呼び出し側のバッファに渡すために pub にしている
+// フィールドは非公開なので、外からは Box<Node<T>> として持つことしかできない
+pub struct Node<T> {
     elem: T,
     next: Link<T>,
 }
 
+// view で head と残りに分けて match できるようにするための型
+// 残りを &List<T> として返すことはできないので、 Iter で表す
+pub enum ListView<'a, T> {
+    Empty,
+    Cons(&'a T, Iter<'a, T>),
+}
+
+// apply_edits で先頭から順に適用する編集操作
+#[derive(Debug, PartialEq, Eq)]
+pub enum Edit<T> {
+    Keep,
+    Replace(T),
+    Insert(T),
+    Delete,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ListError {
+    IndexOutOfBounds { index: usize, len: usize },
+    InvalidPermutation,
+    InvalidRange { start: usize, end: usize },
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "index {} is out of bounds for list of length {}",
+                    index, len
+                )
+            }
+            ListError::InvalidPermutation => write!(f, "order is not a permutation of the list"),
+            ListError::InvalidRange { start, end } => {
+                write!(f, "range start {} is greater than end {}", start, end)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
 impl<T> List<T> {
     pub fn new() -> Self {
         List { head: Link::None }
     }
 
+    // iter の順番がそのまま先頭からの順番になるようにリストを作る
+    // push を使うと逆順になってしまうので、末尾の next を指しながら繋いでいく
+    fn from_ordered<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        let mut tail = &mut list.head;
+        for elem in iter {
+            tail = &mut tail.insert(Box::new(Node { elem, next: None })).next;
+        }
+        list
+    }
+
     pub fn push(&mut self, elem: T) {
         let new_node = Box::new(Node {
             next: self.head.take(),
@@ -27,6 +91,38 @@ impl<T> List<T> {
         self.head = Link::Some(new_node);
     }
 
+    // 等しい要素がまだないときだけ先頭に追加し、追加したかどうかを返す
+    pub fn prepend_if_absent(&mut self, elem: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.iter().any(|e| *e == elem) {
+            return false;
+        }
+        self.push(elem);
+        true
+    }
+
+    // iter の順番を保ったまま先頭に並べるために、後ろから push していく
+    pub fn prepend_iter<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        for elem in iter.into_iter().rev() {
+            self.push(elem);
+        }
+    }
+
+    // prepend_iter の別名
+    pub fn extend_front<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        self.prepend_iter(iter);
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next;
@@ -34,6 +130,56 @@ impl<T> List<T> {
         })
     }
 
+    // head が pred を満たすときだけ pop し、満たさなければ何も変えずに None を返す
+    pub fn pop_if<F: FnOnce(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if self.peek().is_some_and(pred) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    // pop を n 回繰り返すのと同じだが、切り離してから一度だけ繋ぎ変える
+    pub fn shift(&mut self, n: usize) -> usize {
+        let mut removed = 0;
+        let mut cur = &mut self.head;
+        while removed < n {
+            match cur {
+                Some(node) => cur = &mut node.next,
+                None => break,
+            }
+            removed += 1;
+        }
+        let rest = cur.take();
+        drop(List {
+            head: mem::replace(&mut self.head, rest),
+        });
+        removed
+    }
+
+    // pred を満たす先頭の連続部分を切り離して、その個数と一緒に返す
+    pub fn split_off_while<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> (List<T>, usize) {
+        let mut count = 0;
+        let mut cur = &mut self.head;
+        while let Some(node) = cur {
+            if !pred(&node.elem) {
+                break;
+            }
+            count += 1;
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let rest = cur.take();
+        let taken = List {
+            head: mem::replace(&mut self.head, rest),
+        };
+        (taken, count)
+    }
+
+    // head のノードを要素一つのリストとして切り離す
+    pub fn split_head(&mut self) -> Option<List<T>> {
+        self.pop_node().map(|node| List { head: Some(node) })
+    }
+
     fn pop_node(&mut self) -> Link<T> {
         match self.head.take() {
             Link::None => Link::None,
@@ -44,23 +190,706 @@ impl<T> List<T> {
         }
     }
 
+    // 長さは持っていないので、毎回たどって数える
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
     pub fn peek(&self) -> Option<&T> {
         self.head.as_ref().map(|node| &node.elem)
     }
 
+    pub fn peek_pair(&self) -> Option<(&T, &T)> {
+        let first = self.head.as_deref()?;
+        let second = first.next.as_deref()?;
+        Some((&first.elem, &second.elem))
+    }
+
     pub fn peek_mut(&mut self) -> Option<&mut T> {
         self.head.as_mut().map(|node| &mut node.elem)
     }
 
+    // 先頭からちょうど n 個の要素を返す。足りなければ n 個に切り詰めずにエラーにする
+    pub fn peek_exact(&self, n: usize) -> Result<Vec<&T>, ListError> {
+        let elems = self.iter().take(n).collect::<Vec<_>>();
+        if elems.len() < n {
+            return Err(ListError::IndexOutOfBounds {
+                index: n - 1,
+                len: elems.len(),
+            });
+        }
+        Ok(elems)
+    }
+
+    // FFI 用に head の要素へのポインタを返す。空なら null
+    // push や pop などでリストを変更すると dangling になりうるので、変更しない間だけ有効
+    pub fn head_ptr(&self) -> *const T {
+        self.peek().map_or(ptr::null(), |elem| elem as *const T)
+    }
+
+    // 空のときは elem を挿入せず、空のまま None を返す
+    pub fn replace_head(&mut self, elem: T) -> Option<T> {
+        self.peek_mut().map(|head| mem::replace(head, elem))
+    }
+
+    // tail へのポインタは持っていないので、末尾までたどってから置き換える
+    // replace_head と同じく、空のときは何もせずに None を返す
+    pub fn replace_tail(&mut self, elem: T) -> Option<T> {
+        self.iter_mut().last().map(|tail| mem::replace(tail, elem))
+    }
+
+    pub fn position_where<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    // 後ろからはたどれないので、先頭から見ていって最後に満たした位置を返す
+    pub fn rposition_where<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .filter(|(_, elem)| pred(elem))
+            .last()
+            .map(|(index, _)| index)
+    }
+
+    // ソート済みであることを前提に、slice::binary_search と同じ形で結果を返す
+    // ランダムアクセスできないので、実際には先頭から線形に探索する
+    pub fn bisect(&self, value: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        let mut index = 0;
+        for elem in self.iter() {
+            match elem.cmp(value) {
+                Ordering::Less => index += 1,
+                Ordering::Equal => return Ok(index),
+                Ordering::Greater => return Err(index),
+            }
+        }
+        Err(index)
+    }
+
+    // f が false を返したノードを scratch に移すだけで、ここでは deallocate しない
+    // 呼び出し側がまとめて clear したり、プールとして使い回したりできる
+    pub fn retain_batched<F: FnMut(&T) -> bool>(
+        &mut self,
+        mut f: F,
+        scratch: &mut Vec<Box<Node<T>>>,
+    ) {
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            if f(&node.elem) {
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+                scratch.push(node);
+            }
+        }
+    }
+
+    pub fn window_sums(&self, k: usize) -> List<T>
+    where
+        T: Copy + std::ops::Add<Output = T> + Default,
+    {
+        self.windows_map(k, |window| {
+            window.iter().fold(T::default(), |sum, &&elem| sum + elem)
+        })
+    }
+
+    // 長さ k の窓をずらしながら、窓ごとの参照のスライスを f に渡す
+    pub fn windows_map<B, F: FnMut(&[&T]) -> B>(&self, k: usize, f: F) -> List<B> {
+        assert!(k != 0, "window size must be non-zero");
+        let elems = self.iter().collect::<Vec<_>>();
+        List::from_ordered(elems.windows(k).map(f))
+    }
+
+    pub fn replace_all(&mut self, old: &T, new: T) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let mut count = 0;
+        for elem in self.iter_mut().filter(|elem| **elem == *old) {
+            *elem = new.clone();
+            count += 1;
+        }
+        count
+    }
+
+    // ノードを繋ぎ変える安定なマージソート
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        let head = self.head.take();
+        self.head = Self::merge_sort(head, &mut |a: &T, b: &T| f(a) < f(b));
+    }
+
+    // 両方がソート済みであることを前提に、 other のノードを順番に self に差し込む
+    // 等しい要素は self の方が先に来る
+    pub fn merge_in_place(&mut self, mut other: List<T>)
+    where
+        T: Ord,
+    {
+        let head = self.head.take();
+        self.head = Self::merge_links(head, other.head.take(), &mut |a: &T, b: &T| a < b);
+    }
+
+    fn merge_sort<F: FnMut(&T, &T) -> bool>(mut head: Link<T>, is_less: &mut F) -> Link<T> {
+        let mut len = 0;
+        let mut cur = head.as_deref();
+        while let Some(node) = cur {
+            len += 1;
+            cur = node.next.as_deref();
+        }
+        if len < 2 {
+            return head;
+        }
+
+        let mut cur = &mut head;
+        for _ in 0..len / 2 {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let back = cur.take();
+
+        let front = Self::merge_sort(head, is_less);
+        let back = Self::merge_sort(back, is_less);
+        Self::merge_links(front, back, is_less)
+    }
+
+    // 安定にするため、b の先頭が a の先頭より真に小さいときだけ b から取る
+    // 再帰にすると長いリストでスタックが溢れるので、末尾を指しながらループで繋ぐ
+    fn merge_links<F: FnMut(&T, &T) -> bool>(
+        mut a: Link<T>,
+        mut b: Link<T>,
+        is_less: &mut F,
+    ) -> Link<T> {
+        let mut merged = None;
+        let mut tail = &mut merged;
+        while let (Some(x), Some(y)) = (a.as_deref(), b.as_deref()) {
+            let from = if is_less(&y.elem, &x.elem) {
+                &mut b
+            } else {
+                &mut a
+            };
+            let mut node = from.take().unwrap();
+            *from = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+        *tail = a.or(b);
+        merged
+    }
+
+    // 末尾から head に向かって f(elem, acc) で畳み込む右結合の fold
+    // 再帰で書くと長いリストでスタックが溢れるので、参照を Vec に集めて逆順にたどる
+    pub fn foldr<B, F: FnMut(&T, B) -> B>(&self, init: B, mut f: F) -> B {
+        let elems = self.iter().collect::<Vec<_>>();
+        elems.into_iter().rev().fold(init, |acc, elem| f(elem, acc))
+    }
+
+    // リストを消費せずに先頭から畳み込む
+    // 要素が一つだけのときに所有した T を返すため、head だけは clone する
+    pub fn reduce_ref<F: FnMut(&T, &T) -> T>(&self, mut f: F) -> Option<T>
+    where
+        T: Clone,
+    {
+        let mut iter = self.iter();
+        let first = iter.next()?.clone();
+        Some(iter.fold(first, |acc, elem| f(&acc, elem)))
+    }
+
+    // 値ではなくノードそのものを繋ぎ変えて入れ替える
+    // i < j として、 [..i] i [i+1..j] j [j+1..] を [..i] j [i+1..j] i [j+1..] に並べ直す
+    pub fn swap_nodes(&mut self, i: usize, j: usize) {
+        let len = self.len();
+        assert!(i < len, "index {} is out of bounds for length {}", i, len);
+        assert!(j < len, "index {} is out of bounds for length {}", j, len);
+        if i == j {
+            return;
+        }
+        let (i, j) = (i.min(j), i.max(j));
+
+        let mut cur = &mut self.head;
+        for _ in 0..i {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let mut node_i = cur.take().unwrap();
+        let mut middle = node_i.next.take();
+
+        let mut middle_cur = &mut middle;
+        for _ in 0..j - i - 1 {
+            middle_cur = &mut middle_cur.as_mut().unwrap().next;
+        }
+        let mut node_j = middle_cur.take().unwrap();
+        node_i.next = node_j.next.take();
+        *middle_cur = Some(node_i);
+        node_j.next = middle;
+        *cur = Some(node_j);
+    }
+
+    // 各要素を keep_probability の確率で残し、それ以外は取り除く
+    // keep_probability が 0.0 から 1.0 の範囲外か NaN のときは panic する
+    #[cfg(feature = "rand")]
+    pub fn retain_sampled<R: rand::Rng>(&mut self, keep_probability: f64, rng: &mut R) {
+        use rand::RngExt;
+
+        assert!(
+            (0.0..=1.0).contains(&keep_probability),
+            "keep_probability {} is not in [0.0, 1.0]",
+            keep_probability
+        );
+
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            if rng.random_bool(keep_probability) {
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+            }
+        }
+    }
+
+    // 同じ key を持つ要素のうち最初のものだけを、順番を保ったまま残す
+    pub fn unique_by_key<K: Eq + Hash, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        let mut seen = HashSet::new();
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            if seen.insert(key(&node.elem)) {
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+            }
+        }
+    }
+
+    // i64 に変換して足していき、途中で溢れたら None を返す
+    pub fn checked_sum(&self) -> Option<i64>
+    where
+        T: Copy + Into<i64>,
+    {
+        self.iter()
+            .try_fold(0i64, |sum, &elem| sum.checked_add(elem.into()))
+    }
+
+    // 要素は clone せず、参照を Vec に並べて両端から比べる
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let elems = self.iter().collect::<Vec<_>>();
+        elems.iter().eq(elems.iter().rev())
+    }
+
+    // pred を満たす最初の要素が head になるように、それより前の部分を末尾に付け替える
+    pub fn rotate_until<F: FnMut(&T) -> bool>(&mut self, pred: F) -> bool {
+        let Some(index) = self.iter().position(pred) else {
+            return false;
+        };
+        let mut cur = &mut self.head;
+        for _ in 0..index {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let back = cur.take();
+        let front = mem::replace(&mut self.head, back);
+
+        let mut tail = &mut self.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = front;
+        true
+    }
+
+    // 各要素の Display をそのまま並べて [1,2,3] のような JSON の配列にする
+    // 数値などの単純な型向けで、文字列のエスケープはしない。完全なシリアライザではない
+    pub fn to_json(&self) -> String
+    where
+        T: fmt::Display,
+    {
+        let elems = self.iter().map(|elem| elem.to_string()).collect::<Vec<_>>();
+        format!("[{}]", elems.join(","))
+    }
+
+    // 等しい要素が連続しているところでは、最後の一つだけを残す
+    pub fn dedup_keep_last(&mut self)
+    where
+        T: PartialEq,
+    {
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            if node
+                .next
+                .as_ref()
+                .is_some_and(|next| next.elem == node.elem)
+            {
+                *cur = node.next.take();
+            } else {
+                cur = &mut cur.insert(node).next;
+            }
+        }
+    }
+
+    // 直前に残した要素と key が変わった要素だけを残し、同じ key が続く部分は取り除く
+    pub fn retain_changes_by<K: PartialEq, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        let mut last_key = None;
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            let node_key = key(&node.elem);
+            if last_key.as_ref() != Some(&node_key) {
+                last_key = Some(node_key);
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+            }
+        }
+    }
+
+    // key が大きい方から k 個だけを、元の順番のまま残す
+    // 全体をソートせずに k 番目の key を境界として求め、境界と同じ key は先に出てきたものから残す
+    pub fn retain_top_k<K: Ord, F: FnMut(&T) -> K>(&mut self, k: usize, key: F) {
+        let keys = self.iter().map(key).collect::<Vec<_>>();
+        if k >= keys.len() {
+            return;
+        }
+        if k == 0 {
+            drop(List {
+                head: self.head.take(),
+            });
+            return;
+        }
+
+        let mut indices = (0..keys.len()).collect::<Vec<_>>();
+        indices.select_nth_unstable_by(k - 1, |&a, &b| keys[b].cmp(&keys[a]));
+        let threshold = &keys[indices[k - 1]];
+        let mut ties_left = k - keys.iter().filter(|key| *key > threshold).count();
+
+        let mut keys = keys.iter();
+        let mut cur = &mut self.head;
+        while let Some(mut node) = cur.take() {
+            let node_key = keys.next().unwrap();
+            let keep = match node_key.cmp(threshold) {
+                Ordering::Greater => true,
+                Ordering::Equal if ties_left > 0 => {
+                    ties_left -= 1;
+                    true
+                }
+                _ => false,
+            };
+            if keep {
+                cur = &mut cur.insert(node).next;
+            } else {
+                *cur = node.next.take();
+            }
+        }
+    }
+
+    // 今の位置に対して edits を順に適用する。 Insert 以外は今の位置に要素が必要で、なければ panic する
+    // edits はリスト全体をたどる前提なので、使い切った時点で要素が残っていても panic する
+    pub fn apply_edits(&mut self, edits: Vec<Edit<T>>) {
+        let mut cur = &mut self.head;
+        for (index, edit) in edits.into_iter().enumerate() {
+            match edit {
+                Edit::Insert(elem) => {
+                    let node = Box::new(Node {
+                        elem,
+                        next: cur.take(),
+                    });
+                    cur = &mut cur.insert(node).next;
+                }
+                Edit::Delete => {
+                    let Some(mut node) = cur.take() else {
+                        panic!("edit {} is past the end of the list", index);
+                    };
+                    *cur = node.next.take();
+                }
+                edit => {
+                    let Some(node) = cur else {
+                        panic!("edit {} is past the end of the list", index);
+                    };
+                    if let Edit::Replace(elem) = edit {
+                        node.elem = elem;
+                    }
+                    cur = &mut node.next;
+                }
+            }
+        }
+        assert!(cur.is_none(), "edits end before the end of the list");
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> IntoIter<T> {
         IntoIter(self)
     }
 
+    // i 番目から j 番目まで（j も含む）のノードを逆順に繋ぎ変える
+    // j が範囲外か i > j のときは、何も変えずにエラーを返す
+    pub fn checked_reverse_between(&mut self, i: usize, j: usize) -> Result<(), ListError> {
+        let len = self.len();
+        if j >= len {
+            return Err(ListError::IndexOutOfBounds { index: j, len });
+        }
+        if i > j {
+            return Err(ListError::InvalidRange { start: i, end: j });
+        }
+
+        let mut cur = &mut self.head;
+        for _ in 0..i {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let mut rest = cur.take();
+        let mut reversed = None;
+        for _ in i..=j {
+            let mut node = rest.take().unwrap();
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+
+        *cur = reversed;
+        let mut tail = cur;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = rest;
+        Ok(())
+    }
+
+    // 新しい i 番目に、元の order[i] 番目のノードが来るように繋ぎ変える
+    // order が 0..len の並べ替えになっていなければ、何も変えずにエラーを返す
+    pub fn reorder(&mut self, order: &[usize]) -> Result<(), ListError> {
+        let len = self.len();
+        if order.len() != len {
+            return Err(ListError::InvalidPermutation);
+        }
+        let mut seen = vec![false; len];
+        for &index in order {
+            if index >= len || mem::replace(&mut seen[index], true) {
+                return Err(ListError::InvalidPermutation);
+            }
+        }
+
+        let mut nodes = Vec::with_capacity(len);
+        while let Some(node) = self.pop_node() {
+            nodes.push(Some(node));
+        }
+        for &index in order.iter().rev() {
+            let mut node = nodes[index].take().unwrap();
+            node.next = self.head.take();
+            self.head = Some(node);
+        }
+        Ok(())
+    }
+
+    // 先頭から f を適用し、最初に Err が返ったところで止めてそれを返す
+    pub fn try_for_each<E, F: FnMut(&T) -> Result<(), E>>(&self, f: F) -> Result<(), E> {
+        self.iter().try_for_each(f)
+    }
+
+    // ログ出力などのために各要素を f に渡し、リストはそのまま返す
+    // ノードを作り直す必要はないので、借用してたどるだけにしている
+    pub fn inspect_each<F: FnMut(&T)>(self, f: F) -> List<T> {
+        self.iter().for_each(f);
+        self
+    }
+
+    // dst は clear せずに、その後ろに先頭から順に追加する
+    pub fn move_all_into_vec(self, dst: &mut Vec<T>) {
+        dst.extend(self.into_iter());
+    }
+
+    // 順番を保ったまま parts 個の連続した部分リストに分ける
+    // 残りの重みを残りの個数で割った値を目安にして、それを超えそうになったら次のリストに移る
+    // 要素が足りないときは、空のリストで parts 個に揃える
+    pub fn split_by_weight<F: FnMut(&T) -> usize>(
+        self,
+        parts: usize,
+        mut weight: F,
+    ) -> Vec<List<T>> {
+        assert!(parts != 0, "parts must be non-zero");
+        let weighted = self
+            .into_iter()
+            .map(|elem| (weight(&elem), elem))
+            .collect::<Vec<_>>();
+        let mut remaining = weighted.iter().map(|(w, _)| w).sum::<usize>();
+
+        let mut result = Vec::with_capacity(parts);
+        let mut current = Vec::new();
+        let mut current_weight = 0;
+        for (w, elem) in weighted {
+            let parts_left = parts - result.len();
+            let target = remaining.div_ceil(parts_left);
+            if parts_left > 1 && !current.is_empty() && current_weight + w > target {
+                remaining -= current_weight;
+                current_weight = 0;
+                result.push(List::from_ordered(mem::take(&mut current)));
+            }
+            current.push(elem);
+            current_weight += w;
+        }
+        result.push(List::from_ordered(current));
+        result.resize_with(parts, List::new);
+        result
+    }
+
+    // range を先頭からの [start, end) に直す
+    fn range_to_bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| {
+                panic!("range start {} is out of bounds for length {}", start, len)
+            }),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end
+                .checked_add(1)
+                .unwrap_or_else(|| panic!("range end {} is out of bounds for length {}", end, len)),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end,
+            "range start {} is greater than end {}",
+            start,
+            end
+        );
+        assert!(
+            end <= len,
+            "range end {} is out of bounds for length {}",
+            end,
+            len
+        );
+        (start, end)
+    }
+
+    pub fn retain_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = self.range_to_bounds(range);
+        for _ in 0..start {
+            self.pop_node();
+        }
+        let mut cur = &mut self.head;
+        for _ in start..end {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        // 残りを再帰的に drop しないように、List に包んで Drop に任せる
+        drop(List { head: cur.take() });
+    }
+
+    // head が front() になるように、先頭から順に std の LinkedList に移す
+    pub fn into_std_linked_list(self) -> LinkedList<T> {
+        self.into_iter().collect()
+    }
+
+    pub fn from_std_linked_list(list: LinkedList<T>) -> Self {
+        List::from_ordered(list)
+    }
+
+    // 隣り合う要素を (0, 1), (2, 3), ... の組にする。長さが奇数ならそのまま返す
+    pub fn into_pairs(self) -> Result<List<(T, T)>, List<T>> {
+        if !self.len().is_multiple_of(2) {
+            return Err(self);
+        }
+        let mut iter = self.into_iter();
+        Ok(List::from_ordered(std::iter::from_fn(|| {
+            Some((iter.next()?, iter.next()?))
+        })))
+    }
+
+    // cost の累計が budget に収まるあいだだけ先頭から残し、
+    // 最初に budget を超えた要素から後ろをまるごと切り離して返す
+    pub fn retain_within_budget<F: FnMut(&T) -> usize>(
+        &mut self,
+        budget: usize,
+        mut cost: F,
+    ) -> List<T> {
+        let mut total = 0usize;
+        let mut cur = &mut self.head;
+        while let Some(node) = cur {
+            total = total.saturating_add(cost(&node.elem));
+            if total > budget {
+                break;
+            }
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        List { head: cur.take() }
+    }
+
+    // 長さから切る位置を求めて、末尾の n 要素を順番を保ったまま切り離す
+    pub fn split_off_last(&mut self, n: usize) -> List<T> {
+        let keep = self.len().saturating_sub(n);
+        let mut cur = &mut self.head;
+        for _ in 0..keep {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        List { head: cur.take() }
+    }
+
+    // range の部分を切り出して返し、前後はそのまま繋ぎ直す
+    pub fn drain_range<R: RangeBounds<usize>>(&mut self, range: R) -> List<T> {
+        let (start, end) = self.range_to_bounds(range);
+        let mut cur = &mut self.head;
+        for _ in 0..start {
+            cur = &mut cur.as_mut().unwrap().next;
+        }
+        let mut drained = List { head: cur.take() };
+        let mut drained_cur = &mut drained.head;
+        for _ in start..end {
+            drained_cur = &mut drained_cur.as_mut().unwrap().next;
+        }
+        *cur = drained_cur.take();
+        drained
+    }
+
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             // before Rust v1.40, we need to self.head.as_ref().map(|node| &**node)
             next: self.head.as_deref(),
+            remaining: None,
+        }
+    }
+
+    // remaining を n にして、先頭から n 個で止まる Iter を返す
+    pub fn prefix(&self, n: usize) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            remaining: Some(n),
+        }
+    }
+
+    // 長さから開始位置を求めて、そこから末尾までの Iter を返す
+    pub fn suffix(&self, n: usize) -> Iter<'_, T> {
+        let mut next = self.head.as_deref();
+        for _ in 0..self.len().saturating_sub(n) {
+            next = next.and_then(|node| node.next.as_deref());
+        }
+        Iter {
+            next,
+            remaining: None,
+        }
+    }
+
+    // (直前の要素, 今の要素) の組を返す。 head の直前は None
+    pub fn iter_with_prev(&self) -> impl Iterator<Item = (Option<&T>, &T)> + '_ {
+        std::iter::once(None)
+            .chain(self.iter().map(Some))
+            .zip(self.iter())
+    }
+
+    // 空のリストなら何も返さずに終わる
+    pub fn iter_cycle(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().cycle()
+    }
+
+    pub fn view(&self) -> ListView<'_, T> {
+        match self.head.as_deref() {
+            None => ListView::Empty,
+            Some(node) => ListView::Cons(
+                &node.elem,
+                Iter {
+                    next: node.next.as_deref(),
+                    remaining: None,
+                },
+            ),
         }
     }
 
@@ -69,6 +898,61 @@ impl<T> List<T> {
             next: self.head.as_deref_mut(),
         }
     }
+
+    pub fn every_nth(&self, n: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(n != 0, "step must be non-zero");
+        self.iter().step_by(n)
+    }
+}
+
+impl<T: Clone> List<&T> {
+    // 借用しているリストを、要素を clone して所有するリストにする
+    pub fn cloned_list(&self) -> List<T> {
+        List::from_ordered(self.iter().map(|&elem| elem.clone()))
+    }
+}
+
+// lists を順に繋ぎ、隣り合うリストの間にだけ sep の clone を挟む
+// 各リストのノードはそのまま繋ぎ変えるので、新しく確保するのは sep の分だけ
+pub fn join_lists<T: Clone>(lists: Vec<List<T>>, sep: T) -> List<T> {
+    let mut joined = List::new();
+    let mut tail = &mut joined.head;
+    for (index, mut list) in lists.into_iter().enumerate() {
+        if index > 0 {
+            let node = Box::new(Node {
+                elem: sep.clone(),
+                next: None,
+            });
+            tail = &mut tail.insert(node).next;
+        }
+        *tail = list.head.take();
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+    }
+    joined
+}
+
+impl<T> List<Option<T>> {
+    // None を取り除き、 Some の中身だけを順番を保ったまま残す
+    pub fn flatten_options(self) -> List<T> {
+        List::from_ordered(self.into_iter().flatten())
+    }
+}
+
+// ビットは MSB から順に並べる。つまり head が最上位ビットになる
+impl List<bool> {
+    pub fn from_bits(byte: u8) -> List<bool> {
+        List::from_ordered((0..8).rev().map(|shift| (byte >> shift) & 1 == 1))
+    }
+
+    // ちょうど 8 要素のときだけ u8 に戻す
+    pub fn to_byte(&self) -> Option<u8> {
+        if self.len() != 8 {
+            return None;
+        }
+        Some(self.iter().fold(0, |byte, &bit| (byte << 1) | bit as u8))
+    }
 }
 
 impl<T> Drop for List<T> {
@@ -93,11 +977,29 @@ impl<T> Iterator for IntoIter<T> {
 
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
+    // prefix のように途中で止めたいときだけ、残りの個数を Some で持つ
+    remaining: Option<usize>,
+}
+
+// derive だと T: Clone が要求されてしまうので、手で実装する
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            next: self.next,
+            remaining: self.remaining,
+        }
+    }
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(remaining) = &mut self.remaining {
+            if *remaining == 0 {
+                return None;
+            }
+            *remaining -= 1;
+        }
         self.next.map(|node| {
             // self.next = node.next.as_ref().map(|node| &**node);
             // 返り値の型を明示すれば、コンパイラーが勝手に deref とかを apply してくれる
@@ -125,7 +1027,8 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 #[cfg(test)]
 mod test {
     // #[cfg(test)] を入れないと、これが unused 扱いされてしまう
-    use super::List;
+    use super::{Edit, List, ListError, ListView};
+    use std::ops::Bound;
 
     #[test]
     fn basics() {
@@ -214,4 +1117,793 @@ mod test {
         assert_eq!(iter.next(), Some(&mut 2));
         assert_eq!(iter.next(), Some(&mut 1));
     }
+
+    #[test]
+    fn head_ptr() {
+        let mut list: List<i32> = List::new();
+        assert!(list.head_ptr().is_null());
+
+        list.push(1);
+        list.push(2);
+
+        let ptr = list.head_ptr();
+        assert!(!ptr.is_null());
+        assert_eq!(unsafe { *ptr }, 2);
+    }
+
+    #[test]
+    fn bisect() {
+        let list = List::from_ordered(vec![1, 3, 5, 7]);
+        assert_eq!(list.bisect(&5), Ok(2));
+        assert_eq!(list.bisect(&1), Ok(0));
+        assert_eq!(list.bisect(&4), Err(2));
+        assert_eq!(list.bisect(&0), Err(0));
+        assert_eq!(list.bisect(&8), Err(4));
+    }
+
+    #[test]
+    fn retain_batched() {
+        let mut scratch = Vec::with_capacity(4);
+
+        let mut list = List::from_ordered(vec![1, 2, 3, 4, 5]);
+        list.retain_batched(|elem| elem % 2 == 1, &mut scratch);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3, &5]);
+        assert_eq!(scratch.len(), 2);
+
+        let buffer = scratch.as_ptr();
+        scratch.clear();
+
+        let mut list = List::from_ordered(vec![6, 7, 8]);
+        list.retain_batched(|elem| *elem == 7, &mut scratch);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&7]);
+        assert_eq!(scratch.len(), 2);
+        // 二回目の呼び出しでも再確保されずに同じバッファが使われている
+        assert_eq!(scratch.as_ptr(), buffer);
+    }
+
+    #[test]
+    fn window_sums() {
+        let list = List::from_ordered(vec![1, 2, 3, 4]);
+        let sums = list.window_sums(2);
+        assert_eq!(sums.iter().collect::<Vec<_>>(), vec![&3, &5, &7]);
+
+        let sums = list.window_sums(5);
+        assert_eq!(sums.peek(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn window_sums_zero() {
+        List::from_ordered(vec![1, 2]).window_sums(0);
+    }
+
+    #[test]
+    fn replace_all() {
+        let mut list = List::from_ordered(vec![1, 2, 1, 3]);
+        assert_eq!(list.replace_all(&1, 9), 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&9, &2, &9, &3]);
+        assert_eq!(list.replace_all(&1, 9), 0);
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let mut list = List::from_ordered(vec![
+            ("alice", 30),
+            ("bob", 25),
+            ("carol", 30),
+            ("dave", 20),
+            ("eve", 25),
+        ]);
+        list.sort_by_key(|&(_, age)| age);
+        assert_eq!(
+            list.iter().map(|&(name, _)| name).collect::<Vec<_>>(),
+            vec!["dave", "bob", "eve", "alice", "carol"]
+        );
+
+        let mut list: List<(&str, i32)> = List::new();
+        list.sort_by_key(|&(_, age)| age);
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    fn move_all_into_vec() {
+        let mut dst = vec![1, 2];
+        List::from_ordered(vec![3, 4]).move_all_into_vec(&mut dst);
+        assert_eq!(dst, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn every_nth() {
+        let list = List::from_ordered(vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(list.every_nth(3).collect::<Vec<_>>(), vec![&0, &3]);
+        assert_eq!(
+            list.every_nth(1).collect::<Vec<_>>(),
+            list.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn every_nth_zero() {
+        let _ = List::from_ordered(vec![0, 1]).every_nth(0);
+    }
+
+    #[test]
+    fn peek_exact() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(list.peek_exact(3), Ok(vec![&1, &2, &3]));
+        assert_eq!(list.peek_exact(2), Ok(vec![&1, &2]));
+        assert_eq!(list.peek_exact(0), Ok(vec![]));
+        assert_eq!(
+            list.peek_exact(4),
+            Err(ListError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+    }
+
+    #[test]
+    fn prepend_iter() {
+        let mut list = List::from_ordered(vec![1, 2]);
+        list.prepend_iter(vec![3, 4]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &4, &1, &2]);
+        assert_eq!(list.len(), 4);
+
+        let mut list = List::new();
+        assert!(list.is_empty());
+        list.prepend_iter(vec![1]);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn split_by_weight() {
+        let list = List::from_ordered(vec![1, 1, 1, 5]);
+        let parts = list.split_by_weight(2, |&w| w);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].iter().collect::<Vec<_>>(), vec![&1, &1, &1]);
+        assert_eq!(parts[1].iter().collect::<Vec<_>>(), vec![&5]);
+
+        let list = List::from_ordered(vec![1, 2]);
+        let parts = list.split_by_weight(3, |&w| w);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts.iter().map(List::len).sum::<usize>(), 2);
+        assert!(parts[2].is_empty());
+    }
+
+    #[test]
+    fn retain_range() {
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        list.retain_range(1..4);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        list.retain_range(..2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1]);
+
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        list.retain_range(2..);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        list.retain_range(1..=3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn retain_range_out_of_bounds() {
+        List::from_ordered(vec![0, 1, 2]).retain_range(1..4);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn retain_range_inclusive_max() {
+        List::from_ordered(vec![0, 1, 2]).retain_range(..=usize::MAX);
+    }
+
+    #[test]
+    fn reduce_ref() {
+        let list = List::from_ordered(vec![3, 1, 4]);
+        assert_eq!(list.reduce_ref(|a, b| *a.max(b)), Some(4));
+        assert_eq!(list.len(), 3);
+
+        let list: List<i32> = List::new();
+        assert_eq!(list.reduce_ref(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn shift() {
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(list.shift(2), 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3]);
+
+        assert_eq!(list.shift(5), 1);
+        assert!(list.is_empty());
+        assert_eq!(list.shift(1), 0);
+    }
+
+    #[test]
+    fn peek_pair() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(list.peek_pair(), Some((&1, &2)));
+
+        let list = List::from_ordered(vec![1]);
+        assert_eq!(list.peek_pair(), None);
+
+        let list: List<i32> = List::new();
+        assert_eq!(list.peek_pair(), None);
+    }
+
+    #[test]
+    fn swap_nodes() {
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        let addrs = list
+            .iter()
+            .map(|elem| elem as *const i32)
+            .collect::<Vec<_>>();
+
+        list.swap_nodes(0, 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &1, &0, &3, &4]);
+        // 値がコピーされたのではなく、ノードごと移動している
+        assert_eq!(list.head_ptr(), addrs[2]);
+
+        list.swap_nodes(4, 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &1, &0, &4, &3]);
+
+        list.swap_nodes(1, 2);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &0, &1, &4, &3]);
+        let swapped = list
+            .iter()
+            .map(|elem| elem as *const i32)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            swapped,
+            vec![addrs[2], addrs[0], addrs[1], addrs[4], addrs[3]]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_nodes_out_of_bounds() {
+        List::from_ordered(vec![0, 1, 2]).swap_nodes(0, 3);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn retain_sampled() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut list = List::from_ordered(vec![1, 2, 3, 4, 5]);
+        list.retain_sampled(1.0, &mut rng);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(list.len(), 5);
+
+        list.retain_sampled(0.0, &mut rng);
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic(expected = "keep_probability")]
+    fn retain_sampled_out_of_range() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        List::from_ordered(vec![1, 2, 3]).retain_sampled(1.5, &mut rng);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic(expected = "keep_probability")]
+    fn retain_sampled_nan() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        List::from_ordered(vec![1, 2, 3]).retain_sampled(f64::NAN, &mut rng);
+    }
+
+    #[test]
+    fn std_linked_list() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        let std_list = list.into_std_linked_list();
+        assert_eq!(std_list.front(), Some(&1));
+        assert_eq!(std_list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let list = List::from_std_linked_list(std_list);
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn view() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        match list.view() {
+            ListView::Cons(&1, rest) => assert_eq!(rest.collect::<Vec<_>>(), vec![&2, &3]),
+            _ => panic!("expected Cons(&1, [2, 3])"),
+        }
+
+        let list: List<i32> = List::new();
+        assert!(matches!(list.view(), ListView::Empty));
+    }
+
+    #[test]
+    fn extend_front() {
+        let mut list = List::from_ordered(vec![5]);
+        list.extend_front(vec![1, 2, 3]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &5]);
+    }
+
+    #[test]
+    fn into_pairs() {
+        let pairs = match List::from_ordered(vec![1, 2, 3, 4]).into_pairs() {
+            Ok(pairs) => pairs,
+            Err(_) => panic!("expected pairs for an even-length list"),
+        };
+        assert_eq!(pairs.iter().collect::<Vec<_>>(), vec![&(1, 2), &(3, 4)]);
+
+        let list = match List::from_ordered(vec![1, 2, 3]).into_pairs() {
+            Ok(_) => panic!("expected Err for an odd-length list"),
+            Err(list) => list,
+        };
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn unique_by_key() {
+        let mut list = List::from_ordered(vec![(1, "a"), (2, "b"), (1, "c"), (3, "d"), (2, "e")]);
+        list.unique_by_key(|&(id, _)| id);
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec![&(1, "a"), &(2, "b"), &(3, "d")]
+        );
+    }
+
+    #[test]
+    fn prefix_suffix() {
+        let list = List::from_ordered(vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.prefix(2).collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(list.suffix(2).collect::<Vec<_>>(), vec![&4, &5]);
+
+        assert_eq!(list.prefix(10).count(), 5);
+        assert_eq!(list.suffix(10).count(), 5);
+        assert_eq!(list.suffix(0).count(), 0);
+        assert_eq!(list.prefix(0).count(), 0);
+    }
+
+    #[test]
+    fn drain_range() {
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        let drained = list.drain_range(1..3);
+        assert_eq!(drained.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &3, &4]);
+        assert_eq!(list.len(), 3);
+
+        let drained = list.drain_range(..);
+        assert_eq!(drained.len(), 3);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_range_out_of_bounds() {
+        List::from_ordered(vec![0, 1, 2]).drain_range(2..5);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn drain_range_excluded_max() {
+        List::from_ordered(vec![0, 1, 2])
+            .drain_range((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn is_palindrome() {
+        assert!(List::from_ordered(vec![1, 2, 1]).is_palindrome());
+        assert!(!List::from_ordered(vec![1, 2, 3]).is_palindrome());
+        assert!(List::from_ordered(vec![1]).is_palindrome());
+        assert!(List::<i32>::new().is_palindrome());
+    }
+
+    #[test]
+    fn rotate_until() {
+        let mut list = List::from_ordered(vec![1, 2, 3, 4]);
+        assert!(list.rotate_until(|elem| elem % 2 == 0));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &1]);
+
+        assert!(!list.rotate_until(|&elem| elem > 10));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &1]);
+    }
+
+    #[test]
+    fn checked_sum() {
+        assert_eq!(List::from_ordered(vec![1, 2, 3]).checked_sum(), Some(6));
+        assert_eq!(List::<i32>::new().checked_sum(), Some(0));
+        assert_eq!(List::from_ordered(vec![i64::MAX, 1]).checked_sum(), None);
+        assert_eq!(
+            List::from_ordered(vec![i64::MAX, 1, -1]).checked_sum(),
+            None
+        );
+    }
+
+    #[test]
+    fn cloned_list() {
+        let (a, b, c) = (1, 2, 3);
+        let borrowed = List::from_ordered(vec![&a, &b, &c]);
+        let owned: List<i32> = borrowed.cloned_list();
+        assert_eq!(owned.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn position_where() {
+        let list = List::from_ordered(vec![1, 2, 3, 4]);
+        assert_eq!(list.position_where(|elem| elem % 2 == 0), Some(1));
+        assert_eq!(list.rposition_where(|elem| elem % 2 == 0), Some(3));
+        assert_eq!(list.position_where(|&elem| elem > 4), None);
+        assert_eq!(list.rposition_where(|&elem| elem > 4), None);
+    }
+
+    #[test]
+    fn to_json() {
+        assert_eq!(List::from_ordered(vec![1, 2, 3]).to_json(), "[1,2,3]");
+        assert_eq!(List::from_ordered(vec![1.5, -2.0]).to_json(), "[1.5,-2]");
+        assert_eq!(List::<i32>::new().to_json(), "[]");
+    }
+
+    #[test]
+    fn iter_cycle() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(
+            list.iter_cycle().take(7).collect::<Vec<_>>(),
+            vec![&1, &2, &3, &1, &2, &3, &1]
+        );
+
+        let list: List<i32> = List::new();
+        assert_eq!(list.iter_cycle().next(), None);
+    }
+
+    #[test]
+    fn replace_head_tail() {
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(list.replace_head(10), Some(1));
+        assert_eq!(list.replace_tail(30), Some(3));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&10, &2, &30]);
+
+        let mut list = List::new();
+        assert_eq!(list.replace_head(1), None);
+        assert_eq!(list.replace_tail(1), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn retain_within_budget() {
+        let mut list = List::from_ordered(vec![3, 3, 3]);
+        let rest = list.retain_within_budget(7, |&cost| cost);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &3]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![&3]);
+
+        let mut list = List::from_ordered(vec![8, 1]);
+        let rest = list.retain_within_budget(7, |&cost| cost);
+        assert!(list.is_empty());
+        assert_eq!(rest.len(), 2);
+
+        // cost が大きすぎても溢れずに、そこで切り離す
+        let mut list = List::from_ordered(vec![1, usize::MAX, 1]);
+        let rest = list.retain_within_budget(10, |&cost| cost);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(rest.iter().collect::<Vec<_>>(), vec![&usize::MAX, &1]);
+    }
+
+    #[test]
+    fn join_lists() {
+        let lists = vec![
+            List::from_ordered(vec![1, 2]),
+            List::from_ordered(vec![3]),
+            List::from_ordered(vec![4]),
+        ];
+        let joined = super::join_lists(lists, 0);
+        assert_eq!(
+            joined.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &0, &3, &0, &4]
+        );
+
+        assert!(super::join_lists(Vec::<List<i32>>::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn inspect_each() {
+        let mut visited = 0;
+        let list = List::from_ordered(vec![1, 2, 3]).inspect_each(|_| visited += 1);
+        assert_eq!(visited, 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn retain_changes_by() {
+        let mut readings = List::from_ordered(vec![1.1, 0.9, 1.2, 2.1, 1.9, 2.4, 0.8]);
+        readings.retain_changes_by(|&reading: &f64| reading.round() as i32);
+        assert_eq!(readings.iter().collect::<Vec<_>>(), vec![&1.1, &2.1, &0.8]);
+    }
+
+    #[test]
+    fn split_head() {
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        let head = list.split_head().unwrap();
+        assert_eq!(head.iter().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &3]);
+
+        let mut list: List<i32> = List::new();
+        assert!(list.split_head().is_none());
+    }
+
+    #[test]
+    fn bits() {
+        let bits = List::from_bits(0b1010_0101);
+        assert_eq!(
+            bits.iter().copied().collect::<Vec<_>>(),
+            vec![true, false, true, false, false, true, false, true]
+        );
+        assert_eq!(bits.to_byte(), Some(0b1010_0101));
+
+        assert_eq!(List::from_ordered(vec![true; 7]).to_byte(), None);
+        assert_eq!(List::from_ordered(vec![true; 9]).to_byte(), None);
+    }
+
+    #[test]
+    fn retain_top_k() {
+        let mut list = List::from_ordered(vec![("a", 3), ("b", 9), ("c", 1), ("d", 7), ("e", 5)]);
+        list.retain_top_k(2, |&(_, score)| score);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&("b", 9), &("d", 7)]);
+
+        // 境界と同じ key が複数あるときは、先に出てきたものを残す
+        let mut list = List::from_ordered(vec![("a", 5), ("b", 9), ("c", 5), ("d", 5)]);
+        list.retain_top_k(3, |&(_, score)| score);
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec![&("a", 5), &("b", 9), &("c", 5)]
+        );
+
+        list.retain_top_k(0, |&(_, score)| score);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn apply_edits() {
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        list.apply_edits(vec![Edit::Keep, Edit::Replace(9), Edit::Delete]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &9]);
+
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        list.apply_edits(vec![
+            Edit::Insert(0),
+            Edit::Delete,
+            Edit::Keep,
+            Edit::Insert(5),
+            Edit::Keep,
+        ]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &2, &5, &3]);
+
+        let mut list = List::new();
+        list.apply_edits(vec![Edit::Insert(1), Edit::Insert(2)]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_edits_past_end() {
+        let mut list = List::from_ordered(vec![1]);
+        list.apply_edits(vec![Edit::Keep, Edit::Keep]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_edits_shorter_than_list() {
+        let mut list = List::from_ordered(vec![1, 2, 3]);
+        list.apply_edits(vec![Edit::Keep, Edit::Replace(9)]);
+    }
+
+    #[test]
+    fn split_off_while() {
+        let mut list = List::from_ordered(vec![2, 4, 6, 1, 8]);
+        let (evens, count) = list.split_off_while(|elem| elem % 2 == 0);
+        assert_eq!(evens.iter().collect::<Vec<_>>(), vec![&2, &4, &6]);
+        assert_eq!(count, 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &8]);
+
+        let (evens, count) = list.split_off_while(|elem| elem % 2 == 0);
+        assert!(evens.is_empty());
+        assert_eq!(count, 0);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn foldr() {
+        let list = List::from_ordered(vec![1, 2, 3]);
+        assert_eq!(
+            list.foldr(String::from("[]"), |elem, acc| format!(
+                "({} : {})",
+                elem, acc
+            )),
+            "(1 : (2 : (3 : [])))"
+        );
+        // 引き算は結合則を満たさないので、 1 - (2 - (3 - 0)) = 2 になれば右結合になっている
+        assert_eq!(list.foldr(0, |&elem, acc| elem - acc), 2);
+
+        let list: List<i32> = List::new();
+        assert_eq!(list.foldr(7, |&elem, acc| elem + acc), 7);
+    }
+
+    #[test]
+    fn prepend_if_absent() {
+        let mut list = List::from_ordered(vec![2, 3]);
+        assert!(list.prepend_if_absent(1));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek(), Some(&1));
+
+        assert!(!list.prepend_if_absent(3));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    }
+
+    #[test]
+    fn reorder() {
+        let mut list = List::from_ordered(vec!['a', 'b', 'c']);
+        assert_eq!(list.reorder(&[2, 0, 1]), Ok(()));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&'c', &'a', &'b']);
+
+        assert_eq!(list.reorder(&[0, 0, 1]), Err(ListError::InvalidPermutation));
+        assert_eq!(list.reorder(&[0, 1, 3]), Err(ListError::InvalidPermutation));
+        assert_eq!(list.reorder(&[0, 1]), Err(ListError::InvalidPermutation));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&'c', &'a', &'b']);
+    }
+
+    #[test]
+    fn windows_map() {
+        let list = List::from_ordered(vec![1.0, 2.0, 3.0, 4.0]);
+        let averages = list.windows_map(2, |window| {
+            window.iter().copied().sum::<f64>() / window.len() as f64
+        });
+        assert_eq!(averages.iter().collect::<Vec<_>>(), vec![&1.5, &2.5, &3.5]);
+
+        assert!(list.windows_map(5, |window| window.len()).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn windows_map_zero() {
+        List::from_ordered(vec![1, 2]).windows_map(0, |window| window.len());
+    }
+
+    #[test]
+    fn split_off_last() {
+        let mut list = List::from_ordered(vec![1, 2, 3, 4, 5]);
+        let suffix = list.split_off_last(2);
+        assert_eq!(suffix.iter().collect::<Vec<_>>(), vec![&4, &5]);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let suffix = list.split_off_last(10);
+        assert_eq!(suffix.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn checked_reverse_between() {
+        let mut list = List::from_ordered(vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.checked_reverse_between(1, 3), Ok(()));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &3, &2, &1, &4]);
+
+        assert_eq!(list.checked_reverse_between(0, 4), Ok(()));
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &1, &2, &3, &0]);
+
+        assert_eq!(
+            list.checked_reverse_between(2, 5),
+            Err(ListError::IndexOutOfBounds { index: 5, len: 5 })
+        );
+        assert_eq!(
+            list.checked_reverse_between(3, 2),
+            Err(ListError::InvalidRange { start: 3, end: 2 })
+        );
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &1, &2, &3, &0]);
+    }
+
+    #[test]
+    fn merge_in_place() {
+        let mut list = List::from_ordered(vec![1, 3, 5]);
+        list.merge_in_place(List::from_ordered(vec![2, 4, 6]));
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+
+        let mut list = List::new();
+        list.merge_in_place(List::from_ordered(vec![1, 2]));
+        list.merge_in_place(List::new());
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    fn dedup_keep_last() {
+        // key だけで比較して、 tag でどの要素が残ったかを見分ける
+        struct Tagged {
+            key: i32,
+            tag: char,
+        }
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+
+        let mut list = List::from_ordered(
+            [(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (3, 'e')]
+                .into_iter()
+                .map(|(key, tag)| Tagged { key, tag }),
+        );
+        list.dedup_keep_last();
+        assert_eq!(
+            list.iter().map(|elem| elem.key).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            list.iter().map(|elem| elem.tag).collect::<Vec<_>>(),
+            vec!['b', 'c', 'e']
+        );
+    }
+
+    #[test]
+    fn iter_with_prev() {
+        let list = List::from_ordered(vec![10, 20, 30]);
+        assert_eq!(
+            list.iter_with_prev().collect::<Vec<_>>(),
+            vec![(None, &10), (Some(&10), &20), (Some(&20), &30)]
+        );
+
+        let list: List<i32> = List::new();
+        assert_eq!(list.iter_with_prev().next(), None);
+    }
+
+    #[test]
+    fn flatten_options() {
+        let list = List::from_ordered(vec![Some(1), None, Some(2), None]);
+        assert_eq!(
+            list.flatten_options().iter().collect::<Vec<_>>(),
+            vec![&1, &2]
+        );
+    }
+
+    #[test]
+    fn try_for_each() {
+        let list = List::from_ordered(vec![1, 2, 3, 4]);
+
+        let mut visited = Vec::new();
+        let result: Result<(), i32> = list.try_for_each(|&elem| {
+            visited.push(elem);
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(visited, vec![1, 2, 3, 4]);
+
+        let mut visited = Vec::new();
+        let result = list.try_for_each(|&elem| {
+            visited.push(elem);
+            if elem == 2 {
+                Err(elem)
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err(2));
+        assert_eq!(visited, vec![1, 2]);
+    }
+
+    #[test]
+    fn pop_if() {
+        let mut list = List::from_ordered(vec![1, 2]);
+        assert_eq!(list.pop_if(|&elem| elem == 1), Some(1));
+        assert_eq!(list.pop_if(|&elem| elem == 1), None);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2]);
+
+        let mut list: List<i32> = List::new();
+        assert_eq!(list.pop_if(|_| true), None);
+    }
 }
@@ -0,0 +1,198 @@
+use crate::second::{self, List};
+use std::mem;
+
+// 要素が二つまでのあいだはヒープを使わずに enum の中に直接持っておき、
+// 三つ目が push されたときに初めて second::List に移す
+#[derive(Default)]
+pub struct SmallList<T> {
+    repr: Repr<T>,
+}
+
+// Two は (head, next) の順で、 second::List と同じく最後に push したものが先頭
+#[derive(Default)]
+enum Repr<T> {
+    #[default]
+    Empty,
+    One(T),
+    Two(T, T),
+    Many(List<T>),
+}
+
+impl<T> SmallList<T> {
+    pub fn new() -> Self {
+        SmallList { repr: Repr::Empty }
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.repr = match mem::take(&mut self.repr) {
+            Repr::Empty => Repr::One(elem),
+            Repr::One(head) => Repr::Two(elem, head),
+            Repr::Two(head, next) => {
+                let mut list = List::new();
+                list.push(next);
+                list.push(head);
+                list.push(elem);
+                Repr::Many(list)
+            }
+            Repr::Many(mut list) => {
+                list.push(elem);
+                Repr::Many(list)
+            }
+        };
+    }
+
+    // 一度 Many になったら、要素が減っても inline には戻さない
+    pub fn pop(&mut self) -> Option<T> {
+        let (repr, elem) = match mem::take(&mut self.repr) {
+            Repr::Empty => (Repr::Empty, None),
+            Repr::One(head) => (Repr::Empty, Some(head)),
+            Repr::Two(head, next) => (Repr::One(next), Some(head)),
+            Repr::Many(mut list) => {
+                let elem = list.pop();
+                (Repr::Many(list), elem)
+            }
+        };
+        self.repr = repr;
+        elem
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Empty => 0,
+            Repr::One(_) => 1,
+            Repr::Two(_, _) => 2,
+            Repr::Many(list) => list.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        match &self.repr {
+            Repr::Empty => Iter {
+                head: None,
+                next: None,
+                rest: None,
+            },
+            Repr::One(head) => Iter {
+                head: Some(head),
+                next: None,
+                rest: None,
+            },
+            Repr::Two(head, next) => Iter {
+                head: Some(head),
+                next: Some(next),
+                rest: None,
+            },
+            Repr::Many(list) => Iter {
+                head: None,
+                next: None,
+                rest: Some(list.iter()),
+            },
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    head: Option<&'a T>,
+    next: Option<&'a T>,
+    rest: Option<second::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head
+            .take()
+            .or_else(|| self.next.take())
+            .or_else(|| self.rest.as_mut()?.next())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Repr, SmallList};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // テストごとにスレッドが分かれるので、スレッドごとに確保した回数を数える
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct CountingAlloc;
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    fn allocations() -> usize {
+        ALLOCATIONS.with(Cell::get)
+    }
+
+    #[test]
+    fn basics() {
+        let mut list = SmallList::new();
+        assert_eq!(list.pop(), None);
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        list.push(4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn inline_without_allocation() {
+        let before = allocations();
+
+        let mut list = SmallList::new();
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert!(matches!(list.repr, Repr::Two(2, 1)));
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+
+        assert_eq!(allocations(), before);
+    }
+
+    #[test]
+    fn promote_to_many() {
+        let mut list = SmallList::new();
+        list.push(1);
+        list.push(2);
+
+        let before = allocations();
+        list.push(3);
+        assert!(allocations() > before);
+        assert!(matches!(list.repr, Repr::Many(_)));
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    }
+}
@@ -35,7 +35,7 @@ impl<T> List<T> {
         self.head.as_ref().map(|node| &node.elem)
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
         }